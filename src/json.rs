@@ -1,40 +1,149 @@
+use std::cell::Cell;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 #[derive(Debug)]
 pub enum Value {
     Object(BTreeMap<String, Value>),
     Array(Vec<Value>),
     String(String),
+    /// A number token with no `.` or exponent that fits in an `i64`.
+    Integer(i64),
+    /// Any other number token, stored with its full `f64` precision.
     Number(f64),
     True,
     False,
     Null,
 }
 
-struct Context<'a> {
+/// Default recursion limit used by [`parse`], matching serde_json's default.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// A source of input bytes for [`Context`]. Implemented once over an
+/// in-memory slice and once over a buffered [`std::io::Read`], so the parsing
+/// logic in the rest of this module stays agnostic to where the bytes come
+/// from.
+trait Source {
+    /// Returns the next byte without consuming it, or `None` at EOF.
+    fn peek_byte(&mut self) -> Result<Option<u8>, String>;
+    /// Consumes the byte previously returned by `peek_byte`.
+    fn advance(&mut self);
+}
+
+struct SliceSource<'a> {
     idx: usize,
+    src: &'a [u8],
+}
+
+impl<'a> Source for SliceSource<'a> {
+    fn peek_byte(&mut self) -> Result<Option<u8>, String> {
+        Ok(self.src.get(self.idx).copied())
+    }
+
+    fn advance(&mut self) {
+        self.idx += 1;
+    }
+}
+
+/// Feeds bytes from an `impl std::io::Read`, refilling an internal buffer as
+/// it's drained, so the whole input never has to be materialized in memory
+/// up front. Analogous to serde_json's `IoRead`.
+struct IoSource<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: std::io::Read> IoSource<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn refill(&mut self) -> Result<(), String> {
+        if self.pos < self.buf.len() {
+            return Ok(());
+        }
+        self.buf.resize(8192, 0);
+        let n = self
+            .reader
+            .read(&mut self.buf)
+            .map_err(|e| format!("io error: {}", e))?;
+        self.buf.truncate(n);
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl<R: std::io::Read> Source for IoSource<R> {
+    fn peek_byte(&mut self) -> Result<Option<u8>, String> {
+        self.refill()?;
+        Ok(self.buf.get(self.pos).copied())
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+}
+
+struct Context<S> {
     line: usize,
     col: usize,
-    src: &'a [u8],
+    src: S,
+    depth: Rc<Cell<usize>>,
+    max_depth: usize,
+}
+
+impl<'a> Context<SliceSource<'a>> {
+    fn new(src: &'a str, max_depth: usize) -> Self {
+        Self {
+            line: 1,
+            col: 1,
+            src: SliceSource {
+                idx: 0,
+                src: src.as_bytes(),
+            },
+            depth: Rc::new(Cell::new(0)),
+            max_depth,
+        }
+    }
 }
 
-impl<'a> Context<'a> {
-    fn new(src: &'a str) -> Self {
+impl<R: std::io::Read> Context<IoSource<R>> {
+    fn new_reader(reader: R, max_depth: usize) -> Self {
         Self {
-            idx: 0,
             line: 1,
             col: 1,
-            src: src.as_bytes(),
+            src: IoSource::new(reader),
+            depth: Rc::new(Cell::new(0)),
+            max_depth,
         }
     }
+}
 
-    fn peek(&self) -> Result<char, String> {
-        if self.idx >= self.src.len() {
-            return Err(self.error("unexpected EOF"));
+impl<S: Source> Context<S> {
+    /// Enters one level of array/object nesting, returning a guard that
+    /// restores the depth count when it's dropped — on every return path,
+    /// including an early `?`-propagated error — rather than relying on a
+    /// matching hand-written call to decrement it.
+    fn enter(&mut self) -> Result<DepthGuard, String> {
+        if self.depth.get() >= self.max_depth {
+            return Err(self.error("recursion limit exceeded"));
         }
+        self.depth.set(self.depth.get() + 1);
+        Ok(DepthGuard {
+            depth: Rc::clone(&self.depth),
+        })
+    }
 
-        let c = self.src[self.idx];
-        Ok(c as char)
+    fn peek(&mut self) -> Result<char, String> {
+        match self.src.peek_byte()? {
+            Some(c) => Ok(c as char),
+            None => Err(self.error("unexpected EOF")),
+        }
     }
 
     fn next(&mut self) -> Result<char, String> {
@@ -51,12 +160,12 @@ impl<'a> Context<'a> {
         } else {
             self.col += 1;
         }
-        self.idx += 1;
+        self.src.advance();
         Ok(())
     }
 
-    fn is_next(&self) -> bool {
-        self.idx < self.src.len()
+    fn is_next(&mut self) -> bool {
+        matches!(self.src.peek_byte(), Ok(Some(_)))
     }
 
     fn error(&self, err: &str) -> String {
@@ -64,7 +173,20 @@ impl<'a> Context<'a> {
     }
 }
 
-fn parse_whitespace(ctx: &mut Context) -> Result<(), String> {
+/// Returned by [`Context::enter`]; decrements the depth it incremented when
+/// dropped, however the caller's scope is left (normal return, `break`, or an
+/// early `?` on error).
+struct DepthGuard {
+    depth: Rc<Cell<usize>>,
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+fn parse_whitespace<S: Source>(ctx: &mut Context<S>) -> Result<(), String> {
     while ctx.is_next() {
         match ctx.peek()? as u8 {
             0x20 | 0x0a | 0x0d | 0x09 => ctx.consume()?,
@@ -74,7 +196,7 @@ fn parse_whitespace(ctx: &mut Context) -> Result<(), String> {
     Ok(())
 }
 
-fn parse_char(ctx: &mut Context, expected: char) -> Result<(), String> {
+fn parse_char<S: Source>(ctx: &mut Context<S>, expected: char) -> Result<(), String> {
     let n = ctx.next()?;
     if n == expected {
         return Ok(());
@@ -82,14 +204,36 @@ fn parse_char(ctx: &mut Context, expected: char) -> Result<(), String> {
     Err(ctx.error(&format!("expected '{}'", expected)))
 }
 
-fn parse_word(ctx: &mut Context, expected: &str) -> Result<(), String> {
+fn parse_word<S: Source>(ctx: &mut Context<S>, expected: &str) -> Result<(), String> {
     for c in expected.chars() {
         parse_char(ctx, c)?;
     }
     Ok(())
 }
 
-fn parse_string(ctx: &mut Context) -> Result<Value, String> {
+/// Reads exactly four hex digits (as required after `\u`) and returns the
+/// 16-bit code unit they encode.
+fn parse_hex4<S: Source>(ctx: &mut Context<S>) -> Result<u16, String> {
+    let mut n = 0_u16;
+    for i in 0..4 {
+        let d = ctx.next()? as u16;
+        if 48 <= d && d <= 57 {
+            // 0..9
+            n += (d - 48) * 16_u16.pow(4 - i - 1);
+        } else if 65 <= d && d <= 70 {
+            // A..F
+            n += (d - 55) * 16_u16.pow(4 - i - 1);
+        } else if 97 <= d && d <= 102 {
+            // a..f
+            n += (d - 87) * 16_u16.pow(4 - i - 1);
+        } else {
+            return Err(ctx.error("invalid hex digit"));
+        }
+    }
+    Ok(n)
+}
+
+fn parse_string<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
     parse_char(ctx, '"')?;
     let mut s = String::new();
     loop {
@@ -105,23 +249,22 @@ fn parse_string(ctx: &mut Context) -> Result<Value, String> {
                 'r' => s.push(0x0d as char),
                 't' => s.push(0x09 as char),
                 'u' => {
-                    let mut n = 0_u16;
-                    for i in 0..4 {
-                        let d = ctx.next()? as u16;
-                        if 48 <= d && d <= 57 {
-                            // 0..9
-                            n += (d - 48) * 16_u16.pow(4 - i - 1);
-                        } else if 65 <= d && d <= 70 {
-                            // A..F
-                            n += (d - 55) * 16_u16.pow(4 - i - 1);
-                        } else if 97 <= d && d <= 102 {
-                            // a..f
-                            n += (d - 87) * 16_u16.pow(4 - i - 1);
-                        } else {
-                            return Err(ctx.error("invalid hex digit"));
+                    let n = parse_hex4(ctx)?;
+                    let scalar = if (0xD800..=0xDBFF).contains(&n) {
+                        // High surrogate: a low surrogate must immediately follow.
+                        parse_char(ctx, '\\')?;
+                        parse_char(ctx, 'u')?;
+                        let low = parse_hex4(ctx)?;
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err(ctx.error("expected low surrogate after high surrogate"));
                         }
-                    }
-                    s.push(match char::from_u32(n as u32) {
+                        0x10000 + ((n as u32 - 0xD800) << 10) + (low as u32 - 0xDC00)
+                    } else if (0xDC00..=0xDFFF).contains(&n) {
+                        return Err(ctx.error("unpaired low surrogate"));
+                    } else {
+                        n as u32
+                    };
+                    s.push(match char::from_u32(scalar) {
                         Some(c) => c,
                         None => return Err(ctx.error("invalid character")),
                     });
@@ -133,62 +276,79 @@ fn parse_string(ctx: &mut Context) -> Result<Value, String> {
     }
 }
 
-fn parse_digits(ctx: &mut Context) -> Result<f64, String> {
-    let mut num_str = String::new();
-    while ('0'..='9').contains(&ctx.peek()?) {
-        let c = ctx.next()?;
-        num_str.push(c);
+/// Consumes a run of one or more ASCII digits, erroring if none are present.
+fn parse_digit_run<S: Source>(ctx: &mut Context<S>, tok: &mut String) -> Result<(), String> {
+    if !ctx.is_next() || !('0'..='9').contains(&ctx.peek()?) {
+        return Err(ctx.error("expected a digit"));
     }
-    let mut num = 0_f64;
-    for i in 0..num_str.len() {
-        let c = (num_str.chars().nth(i).unwrap() as u8) as f64;
-        num += (10_f64).powf((num_str.len() - i - 1) as f64) * (c - 48_f64);
+    while ctx.is_next() && ('0'..='9').contains(&ctx.peek()?) {
+        tok.push(ctx.next()?);
     }
-    Ok(num)
+    Ok(())
 }
 
-fn parse_number(ctx: &mut Context) -> Result<Value, String> {
-    let mut num;
+/// Scans a full JSON number token (sign, integer part, optional fraction,
+/// optional exponent) into `tok` without interpreting it, per the RFC 8259
+/// grammar: no leading zeros other than a lone `0`, and at least one digit
+/// after `.` and after `e`/`E`. Returns whether the token needs `f64`
+/// (i.e. it has a fraction or exponent).
+fn scan_number<S: Source>(ctx: &mut Context<S>, tok: &mut String) -> Result<bool, String> {
+    let mut is_float = false;
+
+    if ctx.peek()? == '-' {
+        tok.push(ctx.next()?);
+    }
+
     match ctx.peek()? {
-        '-' => {
-            ctx.consume()?;
-            num = -1_f64 * parse_digits(ctx)?;
+        '0' => {
+            tok.push(ctx.next()?);
+            if ctx.is_next() && ('0'..='9').contains(&ctx.peek()?) {
+                return Err(ctx.error("invalid number: leading zero"));
+            }
+        }
+        '1'..='9' => {
+            tok.push(ctx.next()?);
+            while ctx.is_next() && ('0'..='9').contains(&ctx.peek()?) {
+                tok.push(ctx.next()?);
+            }
         }
-        '0'..='9' => num = parse_digits(ctx)?,
-        _ => return Err(ctx.error("expected '-' or '0'..'9'")),
+        _ => return Err(ctx.error("expected '0'..'9'")),
     }
 
-    if ctx.peek()? == '.' {
-        ctx.consume()?;
-        let mut fraction = parse_digits(ctx)?;
-        while fraction > 1_f64 {
-            fraction /= 10_f64;
+    if ctx.is_next() && ctx.peek()? == '.' {
+        is_float = true;
+        tok.push(ctx.next()?);
+        parse_digit_run(ctx, tok)?;
+    }
+
+    if ctx.is_next() && matches!(ctx.peek()?, 'e' | 'E') {
+        is_float = true;
+        tok.push(ctx.next()?);
+        if ctx.is_next() && matches!(ctx.peek()?, '+' | '-') {
+            tok.push(ctx.next()?);
         }
-        num += fraction;
+        parse_digit_run(ctx, tok)?;
     }
 
-    if matches!(ctx.peek()?, 'e' | 'E') {
-        ctx.consume()?;
-        let sign = match ctx.peek()? {
-            '+' => {
-                ctx.consume()?;
-                1_f64
-            }
-            '-' => {
-                ctx.consume()?;
-                -1_f64
-            }
-            _ => 1_f64,
-        };
+    Ok(is_float)
+}
 
-        let exp = sign * parse_digits(ctx)?;
-        num *= 10_f64.powf(exp);
+fn parse_number<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
+    let mut tok = String::new();
+    let is_float = scan_number(ctx, &mut tok)?;
+
+    if !is_float {
+        if let Ok(i) = tok.parse::<i64>() {
+            return Ok(Value::Integer(i));
+        }
     }
 
-    Ok(Value::Number(num))
+    tok.parse::<f64>()
+        .map(Value::Number)
+        .map_err(|_| ctx.error("invalid number"))
 }
 
-fn parse_intrisic(ctx: &mut Context) -> Result<Value, String> {
+fn parse_intrisic<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
     match ctx.peek()? {
         't' => {
             parse_word(ctx, "true")?;
@@ -206,7 +366,8 @@ fn parse_intrisic(ctx: &mut Context) -> Result<Value, String> {
     }
 }
 
-fn parse_array(ctx: &mut Context) -> Result<Value, String> {
+fn parse_array<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
+    let _guard = ctx.enter()?;
     parse_char(ctx, '[')?;
     if ctx.peek()? == ']' {
         ctx.consume()?;
@@ -229,7 +390,8 @@ fn parse_array(ctx: &mut Context) -> Result<Value, String> {
     Ok(Value::Array(vals))
 }
 
-fn parse_object(ctx: &mut Context) -> Result<Value, String> {
+fn parse_object<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
+    let _guard = ctx.enter()?;
     parse_char(ctx, '{')?;
     parse_whitespace(ctx)?;
 
@@ -267,7 +429,7 @@ fn parse_object(ctx: &mut Context) -> Result<Value, String> {
     Ok(Value::Object(obj_vals))
 }
 
-fn parse_value(ctx: &mut Context) -> Result<Value, String> {
+fn parse_value<S: Source>(ctx: &mut Context<S>) -> Result<Value, String> {
     parse_whitespace(ctx)?;
     match ctx.peek()? {
         '{' => parse_object(ctx),
@@ -278,7 +440,314 @@ fn parse_value(ctx: &mut Context) -> Result<Value, String> {
     }
 }
 
+/// Parses a single JSON value, requiring the rest of `src` to be empty
+/// (besides trailing whitespace). Use [`parse_stream`] to read more than one
+/// value out of `src`.
 pub fn parse(src: &str) -> Result<Value, String> {
-    let mut ctx = Context::new(src);
-    parse_value(&mut ctx)
+    parse_with_limit(src, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse`], but fails with an error instead of overflowing the stack
+/// once `max_depth` levels of nested arrays/objects have been entered.
+pub fn parse_with_limit(src: &str, max_depth: usize) -> Result<Value, String> {
+    let mut ctx = Context::new(src, max_depth);
+    let val = parse_value(&mut ctx)?;
+    parse_whitespace(&mut ctx)?;
+    if ctx.is_next() {
+        return Err(ctx.error("trailing characters"));
+    }
+    Ok(val)
+}
+
+/// Parses a single JSON value from an `impl std::io::Read`, such as a socket
+/// or stdin, without requiring the whole input to be buffered as a `String`
+/// up front. Like [`parse`], the rest of the stream must be empty (besides
+/// trailing whitespace).
+pub fn parse_reader<R: std::io::Read>(reader: R) -> Result<Value, String> {
+    parse_reader_with_limit(reader, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_reader`], but fails with an error instead of overflowing the
+/// stack once `max_depth` levels of nested arrays/objects have been entered.
+/// Bounding this is most valuable exactly where `parse_reader` is used —
+/// servers reading untrusted input off a socket or stdin.
+pub fn parse_reader_with_limit<R: std::io::Read>(
+    reader: R,
+    max_depth: usize,
+) -> Result<Value, String> {
+    let mut ctx = Context::new_reader(reader, max_depth);
+    let val = parse_value(&mut ctx)?;
+    parse_whitespace(&mut ctx)?;
+    if ctx.is_next() {
+        return Err(ctx.error("trailing characters"));
+    }
+    Ok(val)
+}
+
+/// Iterates over a sequence of whitespace-separated JSON values in `src`,
+/// such as newline-delimited JSON (NDJSON) logs. Unlike [`parse`], trailing
+/// bytes after a value are not an error — they're parsed as the next item.
+/// Iteration stops (yielding no further items) once EOF is reached after only
+/// whitespace, or after the first malformed value, whose error is yielded
+/// before the iterator ends.
+pub fn parse_stream(src: &str) -> impl Iterator<Item = Result<Value, String>> + '_ {
+    ValueStream {
+        ctx: Context::new(src, DEFAULT_MAX_DEPTH),
+        done: false,
+    }
+}
+
+struct ValueStream<S> {
+    ctx: Context<S>,
+    done: bool,
+}
+
+impl<S: Source> Iterator for ValueStream<S> {
+    type Item = Result<Value, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Err(e) = parse_whitespace(&mut self.ctx) {
+            self.done = true;
+            return Some(Err(e));
+        }
+        if !self.ctx.is_next() {
+            self.done = true;
+            return None;
+        }
+        match parse_value(&mut self.ctx) {
+            Ok(val) => Some(Ok(val)),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Renders `value` back into compact JSON text.
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, None, 0);
+    out
+}
+
+/// Like [`to_string`], but indents nested arrays/objects by `indent` spaces
+/// per level.
+pub fn to_string_pretty(value: &Value, indent: usize) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, Some(indent), 0);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, indent: Option<usize>, depth: usize) {
+    match value {
+        Value::Object(map) => write_object(out, map, indent, depth),
+        Value::Array(vals) => write_array(out, vals, indent, depth),
+        Value::String(s) => write_string(out, s),
+        Value::Integer(i) => out.push_str(&i.to_string()),
+        // JSON has no representation for NaN/Infinity; fall back to `null`
+        // rather than emitting invalid output for a `Value` built by hand.
+        Value::Number(n) if n.is_finite() => out.push_str(&format_float(*n)),
+        Value::Number(_) => out.push_str("null"),
+        Value::True => out.push_str("true"),
+        Value::False => out.push_str("false"),
+        Value::Null => out.push_str("null"),
+    }
+}
+
+/// `f64::to_string` already produces the shortest string that round-trips
+/// back to the same value, but for a whole number it omits the `.` entirely
+/// (`2.0` becomes `"2"`) — re-parsing that would silently turn it into a
+/// `Value::Integer`. Force a `.0` on so the `Number`/`Integer` split survives
+/// a round trip.
+fn format_float(n: f64) -> String {
+    let s = n.to_string();
+    if s.contains(['.', 'e', 'E']) {
+        s
+    } else {
+        format!("{}.0", s)
+    }
+}
+
+fn write_array(out: &mut String, vals: &[Value], indent: Option<usize>, depth: usize) {
+    if vals.is_empty() {
+        out.push_str("[]");
+        return;
+    }
+    out.push('[');
+    for (i, v) in vals.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_newline_indent(out, indent, depth + 1);
+        write_value(out, v, indent, depth + 1);
+    }
+    write_newline_indent(out, indent, depth);
+    out.push(']');
+}
+
+fn write_object(out: &mut String, map: &BTreeMap<String, Value>, indent: Option<usize>, depth: usize) {
+    if map.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push('{');
+    for (i, (key, val)) in map.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_newline_indent(out, indent, depth + 1);
+        write_string(out, key);
+        out.push(':');
+        if indent.is_some() {
+            out.push(' ');
+        }
+        write_value(out, val, indent, depth + 1);
+    }
+    write_newline_indent(out, indent, depth);
+    out.push('}');
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_newline_indent(out: &mut String, indent: Option<usize>, depth: usize) {
+    if let Some(width) = indent {
+        out.push('\n');
+        out.push_str(&" ".repeat(width * depth));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_number_float_round_trips_as_number_not_integer() {
+        let v = parse(&to_string(&Value::Number(2.0))).unwrap();
+        assert!(matches!(v, Value::Number(n) if n == 2.0));
+    }
+
+    #[test]
+    fn to_string_pretty_indents_nested_objects_and_arrays() {
+        let v = parse(r#"{"a":[1,2],"b":{}}"#).unwrap();
+        assert_eq!(
+            to_string_pretty(&v, 2),
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn to_string_pretty_on_empty_array_is_compact() {
+        let v = Value::Array(Vec::new());
+        assert_eq!(to_string_pretty(&v, 4), "[]");
+    }
+
+    #[test]
+    fn write_string_escapes_quotes_backslashes_whitespace_and_control_chars() {
+        let v = Value::String("a\"b\\c\n\t\u{1}d".to_string());
+        assert_eq!(to_string(&v), "\"a\\\"b\\\\c\\n\\t\\u0001d\"");
+        // ... and it round-trips back through the parser unchanged.
+        let reparsed = parse(&to_string(&v)).unwrap();
+        assert!(matches!(reparsed, Value::String(s) if s == "a\"b\\c\n\t\u{1}d"));
+    }
+
+    #[test]
+    fn parse_reader_rejects_trailing_characters() {
+        let err = parse_reader(b"1 2 3".as_slice()).unwrap_err();
+        assert!(err.contains("trailing characters"));
+    }
+
+    #[test]
+    fn parse_reader_with_limit_enforces_recursion_depth() {
+        let nested = "[".repeat(8) + &"]".repeat(8);
+        assert!(parse_reader_with_limit(nested.as_bytes(), 4)
+            .unwrap_err()
+            .contains("recursion limit exceeded"));
+        assert!(parse_reader_with_limit(nested.as_bytes(), 8).is_ok());
+    }
+
+    #[test]
+    fn depth_guard_unwinds_on_error_even_without_an_explicit_exit_call() {
+        // A malformed, unterminated array bubbles its error up through
+        // `parse_value` via `?`, never reaching a hand-written decrement —
+        // the `DepthGuard`'s `Drop` must be what brings `ctx.depth` back to
+        // zero, or a `Context` reused after a recovered error would
+        // over-count its nesting forever.
+        let mut ctx = Context::new("[1,", 128);
+        assert!(parse_value(&mut ctx).is_err());
+        assert_eq!(ctx.depth.get(), 0);
+    }
+
+    #[test]
+    fn rejects_leading_zero_with_clear_error() {
+        assert!(parse("01").unwrap_err().contains("leading zero"));
+        assert!(parse("-01").unwrap_err().contains("leading zero"));
+        assert!(parse(r#"{"a":01}"#).unwrap_err().contains("leading zero"));
+        assert!(matches!(parse("0").unwrap(), Value::Integer(0)));
+        assert!(matches!(parse("0.5").unwrap(), Value::Number(n) if n == 0.5));
+    }
+
+    #[test]
+    fn decodes_surrogate_pair_escape() {
+        // `\uD83D\uDE00` is the UTF-16 surrogate pair for U+1F600 GRINNING FACE.
+        let v = parse(r#""\uD83D\uDE00""#).unwrap();
+        assert!(matches!(v, Value::String(s) if s == "\u{1F600}"));
+    }
+
+    #[test]
+    fn rejects_unpaired_surrogates() {
+        // A high surrogate with no `\u` escape following it at all.
+        assert!(parse(r#""\uD83D""#).unwrap_err().contains("expected '\\'"));
+        // A high surrogate followed by a `\u` escape that isn't a low surrogate.
+        assert!(parse(r#""\uD83D\u0041""#)
+            .unwrap_err()
+            .contains("expected low surrogate"));
+        // A low surrogate with no preceding high surrogate.
+        assert!(parse(r#""\uDE00""#)
+            .unwrap_err()
+            .contains("unpaired low surrogate"));
+    }
+
+    #[test]
+    fn parse_stream_yields_each_whitespace_separated_value() {
+        let vals: Vec<_> = parse_stream("1 2.5 \"a\"\n[true]")
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(vals.len(), 4);
+        assert!(matches!(vals[0], Value::Integer(1)));
+        assert!(matches!(vals[3], Value::Array(ref a) if a.len() == 1));
+    }
+
+    #[test]
+    fn parse_stream_stops_after_malformed_value() {
+        let results: Vec<_> = parse_stream("1 ] 2").collect();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn parse_rejects_trailing_characters_that_parse_stream_accepts() {
+        assert!(parse("1 2").unwrap_err().contains("trailing characters"));
+        assert_eq!(parse_stream("1 2").count(), 2);
+    }
 }